@@ -0,0 +1,244 @@
+//! Optional I/O helpers layered on top of the pure `BaseDir` resolver.
+//!
+//! This module is only compiled when the `fs` feature is enabled. It adds
+//! the filesystem-touching operations most applications actually need:
+//! finding the config or data files that already exist, searching the user
+//! directory first and then falling back through the system search dirs in
+//! priority order, and creating the parent directory of a config file to be
+//! written under the user's `config` directory.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::BaseDir;
+
+impl BaseDir {
+    /// Searches for `relative` under `config`, then under each of
+    /// `config_dirs` in order, returning the first path that exists.
+    pub fn find_config_file(&self, relative: impl AsRef<Path>) -> Option<PathBuf> {
+        Self::find_in(self.config_search_paths(), relative)
+    }
+
+    /// Searches for `relative` under `data`, then under each of `data_dirs`
+    /// in order, returning the first path that exists.
+    pub fn find_data_file(&self, relative: impl AsRef<Path>) -> Option<PathBuf> {
+        Self::find_in(self.data_search_paths(), relative)
+    }
+
+    /// Returns every existing match for `relative` across the config search
+    /// path, in priority order, with repeated paths removed.
+    pub fn list_config_files(&self, relative: impl AsRef<Path>) -> Vec<PathBuf> {
+        Self::list_in(self.config_search_paths(), relative)
+    }
+
+    /// Returns every existing match for `relative` across the data search
+    /// path, in priority order, with repeated paths removed.
+    pub fn list_data_files(&self, relative: impl AsRef<Path>) -> Vec<PathBuf> {
+        Self::list_in(self.data_search_paths(), relative)
+    }
+
+    /// Ensures the parent directory of `relative` exists under `config` and
+    /// returns the writable target path.
+    ///
+    /// This never touches the system `config_dirs`: placement always
+    /// targets the user's own config directory, consistent with the spec's
+    /// guidance that only the `*_HOME` directory is writable.
+    pub fn place_config_file(&self, relative: impl AsRef<Path>) -> io::Result<PathBuf> {
+        Self::place_in(&self.config, relative)
+    }
+
+    fn find_in<'a>(
+        search_paths: impl Iterator<Item = &'a Path>,
+        relative: impl AsRef<Path>,
+    ) -> Option<PathBuf> {
+        let relative = relative.as_ref();
+        search_paths
+            .map(|base| base.join(relative))
+            .find(|candidate| candidate.exists())
+    }
+
+    fn list_in<'a>(
+        search_paths: impl Iterator<Item = &'a Path>,
+        relative: impl AsRef<Path>,
+    ) -> Vec<PathBuf> {
+        let relative = relative.as_ref();
+        let mut found = Vec::new();
+        for candidate in search_paths.map(|base| base.join(relative)) {
+            if candidate.exists() && !found.contains(&candidate) {
+                found.push(candidate);
+            }
+        }
+        found
+    }
+
+    fn place_in(base: &Path, relative: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let target = base.join(relative.as_ref());
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, write};
+
+    fn base_dir(root: &Path) -> BaseDir {
+        BaseDir {
+            home: root.join("home"),
+            config: root.join("home").join(".config"),
+            data: root.join("home").join(".local").join("share"),
+            state: root.join("home").join(".local").join("state"),
+            cache: root.join("home").join(".cache"),
+            runtime: None,
+            bin: root.join("home").join(".local").join("bin"),
+            config_dirs: vec![
+                root.join("etc-xdg-1"),
+                root.join("etc-xdg-2"),
+            ],
+            data_dirs: vec![root.join("usr-share-1"), root.join("usr-share-2")],
+        }
+    }
+
+    #[test]
+    fn find_config_file_prefers_user_dir_over_system_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = base_dir(tmp.path());
+
+        create_dir_all(&dirs.config).unwrap();
+        create_dir_all(&dirs.config_dirs[0]).unwrap();
+        write(dirs.config.join("app.conf"), "user").unwrap();
+        write(dirs.config_dirs[0].join("app.conf"), "system").unwrap();
+
+        assert_eq!(
+            dirs.find_config_file("app.conf"),
+            Some(dirs.config.join("app.conf"))
+        );
+    }
+
+    #[test]
+    fn find_config_file_falls_back_through_config_dirs_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = base_dir(tmp.path());
+
+        create_dir_all(&dirs.config_dirs[0]).unwrap();
+        create_dir_all(&dirs.config_dirs[1]).unwrap();
+        write(dirs.config_dirs[1].join("app.conf"), "second").unwrap();
+
+        assert_eq!(
+            dirs.find_config_file("app.conf"),
+            Some(dirs.config_dirs[1].join("app.conf"))
+        );
+    }
+
+    #[test]
+    fn find_config_file_returns_none_when_missing_everywhere() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = base_dir(tmp.path());
+
+        assert_eq!(dirs.find_config_file("app.conf"), None);
+    }
+
+    #[test]
+    fn find_data_file_prefers_user_dir_over_system_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = base_dir(tmp.path());
+
+        create_dir_all(&dirs.data).unwrap();
+        create_dir_all(&dirs.data_dirs[0]).unwrap();
+        write(dirs.data.join("app.db"), "user").unwrap();
+        write(dirs.data_dirs[0].join("app.db"), "system").unwrap();
+
+        assert_eq!(
+            dirs.find_data_file("app.db"),
+            Some(dirs.data.join("app.db"))
+        );
+    }
+
+    #[test]
+    fn find_data_file_falls_back_through_data_dirs_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = base_dir(tmp.path());
+
+        create_dir_all(&dirs.data_dirs[0]).unwrap();
+        create_dir_all(&dirs.data_dirs[1]).unwrap();
+        write(dirs.data_dirs[1].join("app.db"), "second").unwrap();
+
+        assert_eq!(
+            dirs.find_data_file("app.db"),
+            Some(dirs.data_dirs[1].join("app.db"))
+        );
+    }
+
+    #[test]
+    fn find_data_file_returns_none_when_missing_everywhere() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = base_dir(tmp.path());
+
+        assert_eq!(dirs.find_data_file("app.db"), None);
+    }
+
+    #[test]
+    fn list_config_files_is_priority_ordered_and_deduped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut dirs = base_dir(tmp.path());
+        // Repeat an entry so the dedup behavior is actually exercised.
+        dirs.config_dirs.push(dirs.config_dirs[0].clone());
+
+        create_dir_all(&dirs.config).unwrap();
+        create_dir_all(&dirs.config_dirs[0]).unwrap();
+        create_dir_all(&dirs.config_dirs[1]).unwrap();
+        write(dirs.config.join("app.conf"), "user").unwrap();
+        write(dirs.config_dirs[0].join("app.conf"), "system1").unwrap();
+        write(dirs.config_dirs[1].join("app.conf"), "system2").unwrap();
+
+        assert_eq!(
+            dirs.list_config_files("app.conf"),
+            vec![
+                dirs.config.join("app.conf"),
+                dirs.config_dirs[0].join("app.conf"),
+                dirs.config_dirs[1].join("app.conf"),
+            ]
+        );
+    }
+
+    #[test]
+    fn list_data_files_is_priority_ordered_and_deduped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut dirs = base_dir(tmp.path());
+        // Repeat an entry so the dedup behavior is actually exercised.
+        dirs.data_dirs.push(dirs.data_dirs[0].clone());
+
+        create_dir_all(&dirs.data).unwrap();
+        create_dir_all(&dirs.data_dirs[0]).unwrap();
+        create_dir_all(&dirs.data_dirs[1]).unwrap();
+        write(dirs.data.join("app.db"), "user").unwrap();
+        write(dirs.data_dirs[0].join("app.db"), "system1").unwrap();
+        write(dirs.data_dirs[1].join("app.db"), "system2").unwrap();
+
+        assert_eq!(
+            dirs.list_data_files("app.db"),
+            vec![
+                dirs.data.join("app.db"),
+                dirs.data_dirs[0].join("app.db"),
+                dirs.data_dirs[1].join("app.db"),
+            ]
+        );
+    }
+
+    #[test]
+    fn place_config_file_creates_parent_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = base_dir(tmp.path());
+
+        let target = dirs.place_config_file("nested/app.conf").unwrap();
+
+        assert_eq!(target, dirs.config.join("nested").join("app.conf"));
+        assert!(target.parent().unwrap().is_dir());
+        assert!(!target.exists());
+    }
+}
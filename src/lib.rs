@@ -4,13 +4,17 @@
 //!
 //! `xdgdir` is a spec-compliant crate for locating user and system directories.
 //!
-//! - **Zero I/O**: The library performs no filesystem operations. It is a pure
-//!   path resolver, making it fast, predictable, and suitable for any context,
-//!   including async runtimes.
+//! - **Zero I/O**: The library performs no filesystem operations by default,
+//!   apart from a password-database lookup on Unix when `$HOME` is unset or
+//!   empty. It is a pure path resolver, making it fast, predictable, and
+//!   suitable for any context, including async runtimes.
 //! - **Spec Compliant**: Correctly handles environment variables, empty
 //!   variables, and default fallbacks as defined by the spec.
 //! - **Simple API**: Provides a minimal, ergonomic API for the most common use
 //!   cases.
+//! - **Optional File Lookup**: Enabling the `fs` feature adds a small,
+//!   opt-in API for locating and placing files across the user and system
+//!   search paths.
 //!
 //! ## Examples
 //!
@@ -43,11 +47,22 @@
 use std::{
     env,
     fmt,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
-trait Context {
+mod platform;
+
+use platform::{Platform, PLATFORM};
+
+pub(crate) trait Context {
     fn get(&self, key: &str) -> Option<String>;
+
+    /// An OS-level fallback for the home directory, consulted when `$HOME`
+    /// is absent or empty. Returns `None` by default, so test contexts
+    /// don't need to opt out explicitly.
+    fn home_fallback(&self) -> Option<String> {
+        None
+    }
 }
 
 struct Env;
@@ -55,13 +70,62 @@ impl Context for Env {
     fn get(&self, key: &str) -> Option<String> {
         env::var(key).ok()
     }
+
+    #[cfg(unix)]
+    fn home_fallback(&self) -> Option<String> {
+        home_from_passwd_db()
+    }
+}
+
+/// Resolves the current user's home directory (`pw_dir`) from the system
+/// password database via `getpwuid_r`, for contexts like daemons and cron
+/// jobs where `$HOME` has been stripped from the environment but a real
+/// home directory still exists.
+#[cfg(unix)]
+fn home_from_passwd_db() -> Option<String> {
+    use std::ffi::CStr;
+
+    let buf_size = match unsafe { libc::sysconf(libc::_SC_GETPW_R_SIZE_MAX) } {
+        size if size > 0 => size as usize,
+        _ => 16384,
+    };
+    let mut buf = vec![0 as libc::c_char; buf_size];
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+
+    let status = unsafe {
+        libc::getpwuid_r(
+            libc::geteuid(),
+            &mut passwd,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut result,
+        )
+    };
+
+    if status != 0 || result.is_null() {
+        return None;
+    }
+
+    unsafe { CStr::from_ptr(passwd.pw_dir) }
+        .to_str()
+        .ok()
+        .map(str::to_string)
 }
 
 /// An error that can occur when resolving XDG base directories.
 #[derive(Debug, PartialEq)]
 pub enum Error {
     /// Returned if the `$HOME` environment variable is not set or is empty.
+    ///
+    /// On Unix, this is only returned once the system password database
+    /// has not yielded a home directory either.
+    ///
+    /// On Windows and macOS, see [`Error::HomeNotFound`] instead.
     HomeNotSet,
+    /// Returned on Windows and macOS if no fallback (e.g. `%USERPROFILE%`
+    /// on Windows) could determine a home directory either.
+    HomeNotFound,
     /// Returned if `$HOME` or an `XDG_*` variable contains a relative path,
     /// which is disallowed by the specification.
     ///
@@ -76,6 +140,9 @@ impl fmt::Display for Error {
             Error::HomeNotSet => {
                 write!(f, "$HOME is not set or empty")
             }
+            Error::HomeNotFound => {
+                write!(f, "no home directory could be determined")
+            }
             Error::NotAbsolutePath(key, path) => {
                 write!(
                     f,
@@ -96,30 +163,48 @@ impl std::error::Error for Error {}
 /// paths or `BaseDir::new("app-name")` for application-specific paths.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BaseDir {
-    /// The user's home directory (`$HOME`).
+    /// The user's home directory.
+    /// Resolved from `$HOME`, falling back to the system password database
+    /// on Unix (for daemons, cron jobs, and other contexts where `$HOME`
+    /// has been stripped) and to a platform-native location (e.g.
+    /// `%USERPROFILE%` on Windows) elsewhere.
     pub home: PathBuf,
     /// The user-specific configuration directory.
-    /// Default: `$HOME/.config` (or `$XDG_CONFIG_HOME`).
+    /// Default: `$HOME/.config` on Unix (or `$XDG_CONFIG_HOME`); a
+    /// platform-native location such as `%APPDATA%` elsewhere.
     pub config: PathBuf,
     /// The user-specific data directory.
-    /// Default: `$HOME/.local/share` (or `$XDG_DATA_HOME`).
+    /// Default: `$HOME/.local/share` on Unix (or `$XDG_DATA_HOME`); a
+    /// platform-native location such as `%LOCALAPPDATA%` elsewhere.
     pub data: PathBuf,
     /// The user-specific state directory.
-    /// Default: `$HOME/.local/state` (or `$XDG_STATE_HOME`).
+    /// Default: `$HOME/.local/state` on Unix (or `$XDG_STATE_HOME`); a
+    /// platform-native location elsewhere.
     pub state: PathBuf,
     /// The user-specific cache directory.
-    /// Default: `$HOME/.cache` (or `$XDG_CACHE_HOME`).
+    /// Default: `$HOME/.cache` on Unix (or `$XDG_CACHE_HOME`); a
+    /// platform-native location such as `%LOCALAPPDATA%\cache` elsewhere.
     pub cache: PathBuf,
     /// The user-specific runtime directory (may not be set).
     /// Path: `$XDG_RUNTIME_DIR`.
     pub runtime: Option<PathBuf>,
     /// The directory for user-specific executables.
-    /// Path: `$HOME/.local/bin`.
+    /// Default: `$HOME/.local/bin` (or `$XDG_BIN_HOME`).
     pub bin: PathBuf,
+    /// The preference-ordered set of system-wide configuration directories.
+    /// Default: `/etc/xdg` on Unix (or `$XDG_CONFIG_DIRS`); a
+    /// platform-native location elsewhere (may be empty where the platform
+    /// has no such convention).
+    pub config_dirs: Vec<PathBuf>,
+    /// The preference-ordered set of system-wide data directories.
+    /// Default: `/usr/local/share:/usr/share` on Unix (or
+    /// `$XDG_DATA_DIRS`); a platform-native location elsewhere (may be
+    /// empty where the platform has no such convention).
+    pub data_dirs: Vec<PathBuf>,
 }
 
 impl BaseDir {
-    fn ensure_path(key: &str, path: String) -> Result<PathBuf, Error> {
+    pub(crate) fn ensure_path(key: &str, path: String) -> Result<PathBuf, Error> {
         let path = PathBuf::from(path);
         if path.is_absolute() {
             Ok(path)
@@ -128,11 +213,23 @@ impl BaseDir {
         }
     }
 
+    /// Resolves the home directory from `$HOME`, falling back to a
+    /// platform-native source (e.g. `%USERPROFILE%` on Windows) if `$HOME`
+    /// is absent or empty.
     fn get_home(context: &impl Context) -> Result<PathBuf, Error> {
         match context.get("HOME") {
-            None => Err(Error::HomeNotSet),
-            Some(path) if path.is_empty() => Err(Error::HomeNotSet),
-            Some(path) => Self::ensure_path("HOME", path),
+            None => {}
+            Some(path) if path.is_empty() => {}
+            Some(path) => return Self::ensure_path("HOME", path),
+        }
+
+        if let Some(path) = context.home_fallback() {
+            return Self::ensure_path("HOME", path);
+        }
+
+        match PLATFORM.native_home(context)? {
+            Some(path) => Ok(path),
+            None => Err(PLATFORM.home_not_found_error()),
         }
     }
 
@@ -148,34 +245,54 @@ impl BaseDir {
         }
     }
 
+    /// Parses a colon-separated, preference-ordered search path such as
+    /// `XDG_CONFIG_DIRS`, falling back to the platform-native `default` list
+    /// when the variable is unset or empty.
+    ///
+    /// Unlike `get_path`, a malformed entry does not fail the whole
+    /// resolution: empty entries and non-absolute paths are silently
+    /// dropped, consistent with the fact that a single bad entry in a
+    /// system-wide list shouldn't prevent the rest from being usable.
+    fn get_path_list(
+        context: &impl Context,
+        key: &str,
+        default: Vec<PathBuf>,
+    ) -> Vec<PathBuf> {
+        let value = match context.get(key) {
+            None => return default,
+            Some(path) if path.is_empty() => return default,
+            Some(path) => path,
+        };
+
+        value
+            .split(':')
+            .filter(|entry| !entry.is_empty())
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .collect()
+    }
+
     fn from_context(context: &impl Context) -> Result<Self, Error> {
         let home = Self::get_home(context)?;
-        let bin = home.join(".local").join("bin");
-        let data = Self::get_path(
-            context,
-            "XDG_DATA_HOME",
-            home.join(".local").join("share"),
-        )?;
-        let config = Self::get_path(
-            context, //
-            "XDG_CONFIG_HOME",
-            home.join(".config"),
-        )?;
-        let state = Self::get_path(
+        let bin = Self::get_path(
             context,
-            "XDG_STATE_HOME",
-            home.join(".local").join("state"),
-        )?;
-        let cache = Self::get_path(
-            context, //
-            "XDG_CACHE_HOME",
-            home.join(".cache"),
+            "XDG_BIN_HOME",
+            home.join(".local").join("bin"),
         )?;
+        let native = PLATFORM.native_dirs(context, &home);
+        let data = Self::get_path(context, "XDG_DATA_HOME", native.data)?;
+        let config = Self::get_path(context, "XDG_CONFIG_HOME", native.config)?;
+        let state = Self::get_path(context, "XDG_STATE_HOME", native.state)?;
+        let cache = Self::get_path(context, "XDG_CACHE_HOME", native.cache)?;
         let runtime = match context.get("XDG_RUNTIME_DIR") {
             None => Ok(None),
             Some(path) if path.is_empty() => Ok(None),
             Some(path) => Self::ensure_path("XDG_RUNTIME_DIR", path).map(Some),
         }?;
+        let config_dirs =
+            Self::get_path_list(context, "XDG_CONFIG_DIRS", native.config_dirs);
+        let data_dirs =
+            Self::get_path_list(context, "XDG_DATA_DIRS", native.data_dirs);
 
         Ok(BaseDir {
             home,
@@ -185,6 +302,8 @@ impl BaseDir {
             state,
             cache,
             runtime,
+            config_dirs,
+            data_dirs,
         })
     }
 
@@ -216,8 +335,31 @@ impl BaseDir {
 
         Ok(global_dirs)
     }
+
+    /// Returns the ordered read-search path for configuration files: the
+    /// user's `config` directory first, followed by each of `config_dirs`
+    /// in priority order.
+    ///
+    /// Use this to walk a single canonical ordering when reading a
+    /// resource that may live in either the user or a system location,
+    /// instead of manually concatenating `config` and `config_dirs`.
+    pub fn config_search_paths(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.config.as_path())
+            .chain(self.config_dirs.iter().map(PathBuf::as_path))
+    }
+
+    /// Returns the ordered read-search path for data files: the user's
+    /// `data` directory first, followed by each of `data_dirs` in priority
+    /// order.
+    pub fn data_search_paths(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.data.as_path())
+            .chain(self.data_dirs.iter().map(PathBuf::as_path))
+    }
 }
 
+#[cfg(feature = "fs")]
+mod fs;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,6 +423,64 @@ mod tests {
         assert_eq!(result.bin, PathBuf::from("/home/user/.local/bin"));
     }
 
+    #[test]
+    fn xdg_bin_home_not_absolute() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_BIN_HOME", "some/dir");
+        let result = BaseDir::from_context(&context);
+        let error = result.unwrap_err();
+        let report = format!("{}", error);
+        assert_eq!(
+            error,
+            Error::NotAbsolutePath("XDG_BIN_HOME".into(), "some/dir".into())
+        );
+        assert_eq!(report, "XDG_BIN_HOME=\"some/dir\" is not absolute path");
+    }
+
+    #[test]
+    fn xdg_bin_home_valid() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_BIN_HOME", "/some/dir");
+        let result = BaseDir::from_context(&context).unwrap();
+        assert_eq!(result.bin, PathBuf::from("/some/dir"));
+    }
+
+    #[test]
+    fn config_search_paths_orders_home_before_system_dirs() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_CONFIG_DIRS", "/etc/xdg1:/etc/xdg2");
+        let result = BaseDir::from_context(&context).unwrap();
+        let search_paths: Vec<_> = result.config_search_paths().collect();
+        assert_eq!(
+            search_paths,
+            vec![
+                Path::new("/home/user/.config"),
+                Path::new("/etc/xdg1"),
+                Path::new("/etc/xdg2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn data_search_paths_orders_home_before_system_dirs() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_DATA_DIRS", "/data1:/data2");
+        let result = BaseDir::from_context(&context).unwrap();
+        let search_paths: Vec<_> = result.data_search_paths().collect();
+        assert_eq!(
+            search_paths,
+            vec![
+                Path::new("/home/user/.local/share"),
+                Path::new("/data1"),
+                Path::new("/data2"),
+            ]
+        );
+    }
+
     #[test]
     fn xdg_data_home_not_set() {
         let mut context = HashMap::new();
@@ -449,4 +649,71 @@ mod tests {
         let result = BaseDir::from_context(&context).unwrap();
         assert_eq!(result.runtime, Some(PathBuf::from("/run/user/1000")));
     }
+
+    #[test]
+    fn xdg_config_dirs_not_set() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        let result = BaseDir::from_context(&context).unwrap();
+        assert_eq!(result.config_dirs, vec![PathBuf::from("/etc/xdg")]);
+    }
+
+    #[test]
+    fn xdg_config_dirs_empty() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_CONFIG_DIRS", "");
+        let result = BaseDir::from_context(&context).unwrap();
+        assert_eq!(result.config_dirs, vec![PathBuf::from("/etc/xdg")]);
+    }
+
+    #[test]
+    fn xdg_config_dirs_valid() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_CONFIG_DIRS", "/etc/xdg1:/etc/xdg2");
+        let result = BaseDir::from_context(&context).unwrap();
+        assert_eq!(
+            result.config_dirs,
+            vec![PathBuf::from("/etc/xdg1"), PathBuf::from("/etc/xdg2")]
+        );
+    }
+
+    #[test]
+    fn xdg_config_dirs_skips_invalid_entries() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_CONFIG_DIRS", "/etc/xdg1::some/dir:/etc/xdg2");
+        let result = BaseDir::from_context(&context).unwrap();
+        assert_eq!(
+            result.config_dirs,
+            vec![PathBuf::from("/etc/xdg1"), PathBuf::from("/etc/xdg2")]
+        );
+    }
+
+    #[test]
+    fn xdg_data_dirs_not_set() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        let result = BaseDir::from_context(&context).unwrap();
+        assert_eq!(
+            result.data_dirs,
+            vec![
+                PathBuf::from("/usr/local/share"),
+                PathBuf::from("/usr/share"),
+            ]
+        );
+    }
+
+    #[test]
+    fn xdg_data_dirs_valid() {
+        let mut context = HashMap::new();
+        context.insert("HOME", "/home/user");
+        context.insert("XDG_DATA_DIRS", "/data1:/data2");
+        let result = BaseDir::from_context(&context).unwrap();
+        assert_eq!(
+            result.data_dirs,
+            vec![PathBuf::from("/data1"), PathBuf::from("/data2")]
+        );
+    }
 }
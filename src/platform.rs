@@ -0,0 +1,342 @@
+//! Native, non-XDG fallbacks used when the relevant `XDG_*` variable (and,
+//! for `home`, `$HOME` itself) is absent from the environment.
+//!
+//! `XDG_*` overrides are always honored first on every platform; this
+//! module only supplies the default that applies when they're unset, so a
+//! single code path (`BaseDir::new`) works portably instead of requiring
+//! callers to `cfg`-gate XDG usage themselves.
+//!
+//! The `Windows`, `MacOs`, and `Unix` implementations below are always
+//! compiled, regardless of the host target: only the choice of which one
+//! backs `PLATFORM` is `cfg`-gated. Their tests fake `Context` with a
+//! `HashMap`, as `lib.rs`'s tests do, but each platform's tests are gated
+//! to the matching `target_os` (see the note in `tests` below), so each
+//! implementation is only exercised when building for its own OS.
+
+use std::path::{Path, PathBuf};
+
+use crate::{Context, Error};
+
+/// The native defaults for `config`, `data`, `state`, `cache`,
+/// `config_dirs`, and `data_dirs`, used only when the corresponding
+/// `XDG_*` variable is unset or empty.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct NativeDirs {
+    pub(crate) config: PathBuf,
+    pub(crate) data: PathBuf,
+    pub(crate) state: PathBuf,
+    pub(crate) cache: PathBuf,
+    pub(crate) config_dirs: Vec<PathBuf>,
+    pub(crate) data_dirs: Vec<PathBuf>,
+}
+
+/// Resolves platform-native locations for the pieces the XDG spec leaves
+/// undefined outside of Unix: where the home directory lives, and what the
+/// base directories default to when no override is present.
+pub(crate) trait Platform {
+    /// Resolves the home directory from native, non-`$HOME` environment
+    /// variables. Returns `Ok(None)` if this platform has no such fallback.
+    fn native_home(&self, context: &impl Context) -> Result<Option<PathBuf>, Error>;
+
+    /// Returns the native defaults for `config`, `data`, `state`, `cache`,
+    /// `config_dirs`, and `data_dirs`, given the resolved home directory.
+    fn native_dirs(&self, context: &impl Context, home: &Path) -> NativeDirs;
+
+    /// The error to report when no home directory could be determined at
+    /// all, phrased appropriately for this platform.
+    fn home_not_found_error(&self) -> Error;
+}
+
+// Only ever constructed via `PLATFORM` on Windows itself, or directly from
+// that target's `#[cfg(test)]` code; `#[allow(dead_code)]` keeps it compiling
+// unconditionally (see the module doc) without tripping `dead_code` on the
+// other two targets' builds.
+#[allow(dead_code)]
+pub(crate) struct Windows;
+
+impl Platform for Windows {
+    fn native_home(&self, context: &impl Context) -> Result<Option<PathBuf>, Error> {
+        match context.get("USERPROFILE") {
+            None => Ok(None),
+            Some(path) if path.is_empty() => Ok(None),
+            Some(path) => crate::BaseDir::ensure_path("USERPROFILE", path).map(Some),
+        }
+    }
+
+    fn native_dirs(&self, context: &impl Context, home: &Path) -> NativeDirs {
+        let app_data = context
+            .get("APPDATA")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join("AppData").join("Roaming"));
+        let local_app_data = context
+            .get("LOCALAPPDATA")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join("AppData").join("Local"));
+
+        // Windows has no per-user/system split the way XDG does: the closest
+        // system-wide analogue to `config`/`data` is `%ProgramData%`, used
+        // for both, since there's no separate convention for the two.
+        let program_data = context
+            .get("ProgramData")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"));
+
+        NativeDirs {
+            config: app_data.clone(),
+            data: local_app_data.clone(),
+            state: local_app_data.clone(),
+            cache: local_app_data.join("cache"),
+            config_dirs: vec![program_data.clone()],
+            data_dirs: vec![program_data],
+        }
+    }
+
+    fn home_not_found_error(&self) -> Error {
+        Error::HomeNotFound
+    }
+}
+
+// Same rationale as `Windows` above.
+#[allow(dead_code)]
+pub(crate) struct MacOs;
+
+impl Platform for MacOs {
+    fn native_home(&self, _context: &impl Context) -> Result<Option<PathBuf>, Error> {
+        Ok(None)
+    }
+
+    fn native_dirs(&self, _context: &impl Context, home: &Path) -> NativeDirs {
+        let application_support = home.join("Library").join("Application Support");
+        // `/Library/Application Support` is the system-wide counterpart,
+        // serving as the closest analogue to both `config_dirs` and
+        // `data_dirs` since macOS doesn't distinguish the two.
+        let system_application_support =
+            PathBuf::from("/Library").join("Application Support");
+
+        NativeDirs {
+            config: application_support.clone(),
+            data: application_support,
+            state: home.join("Library").join("State"),
+            cache: home.join("Library").join("Caches"),
+            config_dirs: vec![system_application_support.clone()],
+            data_dirs: vec![system_application_support],
+        }
+    }
+
+    fn home_not_found_error(&self) -> Error {
+        Error::HomeNotFound
+    }
+}
+
+pub(crate) struct Unix;
+
+impl Platform for Unix {
+    fn native_home(&self, _context: &impl Context) -> Result<Option<PathBuf>, Error> {
+        Ok(None)
+    }
+
+    fn native_dirs(&self, _context: &impl Context, home: &Path) -> NativeDirs {
+        NativeDirs {
+            config: home.join(".config"),
+            data: home.join(".local").join("share"),
+            state: home.join(".local").join("state"),
+            cache: home.join(".cache"),
+            config_dirs: vec![PathBuf::from("/etc/xdg")],
+            data_dirs: vec![
+                PathBuf::from("/usr/local/share"),
+                PathBuf::from("/usr/share"),
+            ],
+        }
+    }
+
+    fn home_not_found_error(&self) -> Error {
+        Error::HomeNotSet
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) const PLATFORM: Windows = Windows;
+
+#[cfg(target_os = "macos")]
+pub(crate) const PLATFORM: MacOs = MacOs;
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub(crate) const PLATFORM: Unix = Unix;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    // `PathBuf` equality and `Path::is_absolute` are defined in terms of the
+    // *host's* path syntax, not the syntax of the platform a `Platform` impl
+    // models — so e.g. `C:\Users\x` is only absolute, and joins only use
+    // `\`, when actually compiled for Windows. Each platform's tests are
+    // gated to the matching `target_os` so they exercise real path
+    // semantics under cross-compiled CI instead of giving false failures
+    // (or false passes) on a host that models paths differently.
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_native_home_uses_userprofile() {
+        let mut context = HashMap::new();
+        context.insert("USERPROFILE", r"C:\Users\fake_user");
+        let home = Windows.native_home(&context).unwrap();
+        assert_eq!(home, Some(PathBuf::from(r"C:\Users\fake_user")));
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_native_home_empty_returns_none() {
+        let context: HashMap<&str, &str> = HashMap::new();
+        let home = Windows.native_home(&context).unwrap();
+        assert_eq!(home, None);
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_native_home_not_absolute_errors() {
+        let mut context = HashMap::new();
+        context.insert("USERPROFILE", "relative\\path");
+        let error = Windows.native_home(&context).unwrap_err();
+        assert_eq!(
+            error,
+            Error::NotAbsolutePath("USERPROFILE".into(), "relative\\path".into())
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_native_dirs_uses_appdata_and_localappdata() {
+        let mut context = HashMap::new();
+        context.insert("APPDATA", r"C:\Users\fake_user\AppData\Roaming");
+        context.insert("LOCALAPPDATA", r"C:\Users\fake_user\AppData\Local");
+        let dirs = Windows.native_dirs(&context, Path::new(r"C:\Users\fake_user"));
+
+        assert_eq!(dirs.config, PathBuf::from(r"C:\Users\fake_user\AppData\Roaming"));
+        assert_eq!(dirs.data, PathBuf::from(r"C:\Users\fake_user\AppData\Local"));
+        assert_eq!(dirs.state, PathBuf::from(r"C:\Users\fake_user\AppData\Local"));
+        assert_eq!(
+            dirs.cache,
+            PathBuf::from(r"C:\Users\fake_user\AppData\Local\cache")
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_native_dirs_falls_back_when_appdata_unset() {
+        let context: HashMap<&str, &str> = HashMap::new();
+        let dirs = Windows.native_dirs(&context, Path::new(r"C:\Users\fake_user"));
+
+        assert_eq!(
+            dirs.config,
+            PathBuf::from(r"C:\Users\fake_user")
+                .join("AppData")
+                .join("Roaming")
+        );
+        assert_eq!(
+            dirs.data,
+            PathBuf::from(r"C:\Users\fake_user")
+                .join("AppData")
+                .join("Local")
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_native_dirs_config_dirs_and_data_dirs_use_program_data() {
+        let mut context = HashMap::new();
+        context.insert("ProgramData", r"C:\ProgramData");
+        let dirs = Windows.native_dirs(&context, Path::new(r"C:\Users\fake_user"));
+
+        assert_eq!(dirs.config_dirs, vec![PathBuf::from(r"C:\ProgramData")]);
+        assert_eq!(dirs.data_dirs, vec![PathBuf::from(r"C:\ProgramData")]);
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_home_not_found_error() {
+        assert_eq!(Windows.home_not_found_error(), Error::HomeNotFound);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn macos_native_home_has_no_fallback() {
+        let context: HashMap<&str, &str> = HashMap::new();
+        assert_eq!(MacOs.native_home(&context).unwrap(), None);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn macos_native_dirs_uses_application_support() {
+        let context: HashMap<&str, &str> = HashMap::new();
+        let dirs = MacOs.native_dirs(&context, Path::new("/Users/fake_user"));
+
+        assert_eq!(
+            dirs.config,
+            PathBuf::from("/Users/fake_user/Library/Application Support")
+        );
+        assert_eq!(
+            dirs.data,
+            PathBuf::from("/Users/fake_user/Library/Application Support")
+        );
+        assert_eq!(
+            dirs.state,
+            PathBuf::from("/Users/fake_user/Library/State")
+        );
+        assert_eq!(
+            dirs.cache,
+            PathBuf::from("/Users/fake_user/Library/Caches")
+        );
+        assert_eq!(
+            dirs.config_dirs,
+            vec![PathBuf::from("/Library/Application Support")]
+        );
+        assert_eq!(
+            dirs.data_dirs,
+            vec![PathBuf::from("/Library/Application Support")]
+        );
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn macos_home_not_found_error() {
+        assert_eq!(MacOs.home_not_found_error(), Error::HomeNotFound);
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn unix_native_home_has_no_fallback() {
+        let context: HashMap<&str, &str> = HashMap::new();
+        assert_eq!(Unix.native_home(&context).unwrap(), None);
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn unix_native_dirs_uses_dotfiles() {
+        let context: HashMap<&str, &str> = HashMap::new();
+        let dirs = Unix.native_dirs(&context, Path::new("/home/fake_user"));
+
+        assert_eq!(dirs.config, PathBuf::from("/home/fake_user/.config"));
+        assert_eq!(dirs.data, PathBuf::from("/home/fake_user/.local/share"));
+        assert_eq!(dirs.state, PathBuf::from("/home/fake_user/.local/state"));
+        assert_eq!(dirs.cache, PathBuf::from("/home/fake_user/.cache"));
+        assert_eq!(dirs.config_dirs, vec![PathBuf::from("/etc/xdg")]);
+        assert_eq!(
+            dirs.data_dirs,
+            vec![
+                PathBuf::from("/usr/local/share"),
+                PathBuf::from("/usr/share"),
+            ]
+        );
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn unix_home_not_found_error() {
+        assert_eq!(Unix.home_not_found_error(), Error::HomeNotSet);
+    }
+}
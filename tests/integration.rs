@@ -30,10 +30,42 @@ fn test_overrides() {
 }
 
 #[test]
-fn test_failures() {
+fn test_home_falls_back_to_passwd_db() {
+    // On Unix, an unset $HOME no longer fails outright: the current user's
+    // home directory is looked up in the system password database, so the
+    // command succeeds with a non-empty, absolute `home`. That lookup has
+    // nothing to find in containers that run as a numeric UID with no
+    // matching `/etc/passwd` entry, so skip rather than depend on the
+    // ambient environment having one.
+    if !current_uid_has_passwd_entry() {
+        return;
+    }
+
     let mut cmd = Command::cargo_bin("printdirs").unwrap();
     cmd.env_clear().arg("my-app");
-    cmd.assert()
-        .failure()
-        .stderr(contains("$HOME is not set or empty"));
+    cmd.assert().success().stdout(contains("home=/"));
+}
+
+#[cfg(unix)]
+fn current_uid_has_passwd_entry() -> bool {
+    let mut buf = vec![0 as libc::c_char; 16384];
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+
+    let status = unsafe {
+        libc::getpwuid_r(
+            libc::geteuid(),
+            &mut passwd,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut result,
+        )
+    };
+
+    status == 0 && !result.is_null()
+}
+
+#[cfg(not(unix))]
+fn current_uid_has_passwd_entry() -> bool {
+    false
 }
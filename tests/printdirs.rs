@@ -21,6 +21,12 @@ fn main() {
             if let Some(runtime) = dirs.runtime {
                 println!("runtime={}", runtime.display());
             }
+            for dir in &dirs.config_dirs {
+                println!("config_dirs={}", dir.display());
+            }
+            for dir in &dirs.data_dirs {
+                println!("data_dirs={}", dir.display());
+            }
         }
         Err(e) => {
             // If it fails, print the error to stderr and exit.